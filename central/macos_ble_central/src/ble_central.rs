@@ -1,19 +1,72 @@
+use crate::codec::{CborCodec, JsonCodec, PayloadCodec, ProtobufCodec, RawCodec, DecodedPayload};
 use crate::error::BleError;
-use btleplug::api::{Central, Characteristic, Manager as _, Peripheral as _, ScanFilter};
-use btleplug::platform::{Adapter, Manager, Peripheral};
-use futures::stream::StreamExt;
-use serde_json::Value;
+use btleplug::api::{
+    BDAddr, Central, CentralEvent, Characteristic, Manager as _, Peripheral as _, ScanFilter,
+    WriteType,
+};
+use btleplug::platform::{Adapter, Manager, Peripheral, PeripheralId};
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
 use tokio::time::{sleep, timeout, Duration};
 use uuid::Uuid;
 use tracing::{debug, error, info, instrument, warn /*trace*/};
 
 
+/// How consecutive notification payloads are reassembled into whole frames.
+///
+/// A single BLE notification is capped near the negotiated MTU (often ~20
+/// bytes), so a JSON object larger than that arrives split across several
+/// notifications. `FramingMode` tells `BleCentral` how to find frame
+/// boundaries in that byte stream before handing a complete frame to the
+/// configured codec.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FramingMode {
+    /// Each notification is treated as a complete frame on its own — no
+    /// cross-notification accumulation. This is the crate's original
+    /// behavior (one packet, one JSON object) and stays the default so
+    /// existing peripherals that don't delimit or length-prefix their
+    /// frames keep working unchanged.
+    #[default]
+    WholePacket,
+    /// The first two bytes of a frame are a big-endian `u16` length prefix;
+    /// accumulate bytes until that many payload bytes have arrived.
+    LengthPrefixed,
+    /// Frames are separated by a single delimiter byte (e.g. `b'\n'` or NUL).
+    Delimited(u8),
+}
+
+/// Which `PayloadCodec` `BleCentral` should build and use on reassembled frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum CodecKind {
+    /// UTF-8 JSON — the crate's original behavior.
+    #[default]
+    Json,
+    /// No decoding; frames are passed through as raw bytes.
+    Raw,
+    /// CBOR via `ciborium`.
+    Cbor,
+    /// Length-delimited protobuf, as Meshtastic's radio protocol uses;
+    /// the caller parses the bytes with its own generated message types.
+    Protobuf,
+}
+
 #[derive(Debug)]
 pub struct BleConfig {
-    pub scan_retries:     u32,
-    pub scan_interval:    Duration,
-    pub notify_timeout:   Duration,
+    pub scan_retries:      u32,
+    pub scan_interval:     Duration,
+    pub notify_timeout:    Duration,
+    /// Initial delay before the first reconnect attempt; doubles on each
+    /// consecutive failure up to `reconnect_backoff_max`.
     pub reconnect_backoff: Duration,
+    /// Ceiling on the exponentially-growing reconnect delay.
+    pub reconnect_backoff_max: Duration,
+    pub framing:           FramingMode,
+    pub max_frame_size:    usize,
+    /// When `true`, `BleCentral` connects to every peripheral advertising
+    /// the configured service instead of stopping at the first match.
+    pub multi_peripheral:  bool,
+    pub codec:             CodecKind,
 }
 
 impl Default for BleConfig {
@@ -23,44 +76,203 @@ impl Default for BleConfig {
             scan_interval:     Duration::from_secs(1),
             notify_timeout:    Duration::from_secs(10),
             reconnect_backoff: Duration::from_secs(5),
+            reconnect_backoff_max: Duration::from_secs(60),
+            framing:           FramingMode::default(),
+            max_frame_size:    4096,
+            multi_peripheral:  false,
+            codec:             CodecKind::default(),
+        }
+    }
+}
+
+/// One peripheral seen while scanning, carrying enough advertisement data to
+/// rank candidates before connecting (mirrors loranto's `ScanResult`).
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    pub address: BDAddr,
+    pub local_name: Option<String>,
+    pub rssi: i16,
+    pub manufacturer_data: HashMap<u16, Vec<u8>>,
+}
+
+/// A single reassembled, codec-decoded notification, ready for a downstream
+/// consumer — this is what turns the crate from a log line into a library.
+#[derive(Debug, Clone)]
+pub struct NotificationEvent {
+    pub source: BDAddr,
+    pub characteristic: Uuid,
+    pub raw: Vec<u8>,
+    pub payload: DecodedPayload,
+}
+
+/// Per-peripheral connection state: the GATT handle plus whatever this crate
+/// has discovered/accumulated for it so far.
+struct ConnectedPeripheral {
+    peripheral: Peripheral,
+    characteristic: Option<Characteristic>,
+    write_characteristic: Option<Characteristic>,
+    reassembly_buf: Vec<u8>,
+}
+
+impl ConnectedPeripheral {
+    fn new(peripheral: Peripheral) -> Self {
+        Self {
+            peripheral,
+            characteristic: None,
+            write_characteristic: None,
+            reassembly_buf: Vec::new(),
         }
     }
+
+    /// Feed freshly-received notification bytes into this peripheral's
+    /// reassembly buffer and return every whole frame that became available
+    /// as a result.
+    ///
+    /// A framing error or a buffer that grows past `max_frame_size` drops
+    /// the in-flight frame (with a warning) instead of wedging the session —
+    /// a single corrupt fragment shouldn't take down the stream.
+    fn reassemble(&mut self, bytes: &[u8], framing: FramingMode, max_frame_size: usize) -> Vec<Vec<u8>> {
+        reassemble_frames(&mut self.reassembly_buf, bytes, framing, max_frame_size)
+    }
+}
+
+/// The actual reassembly step behind `ConnectedPeripheral::reassemble`,
+/// pulled out as a free function over a plain buffer so it can be unit
+/// tested without a live `Peripheral` handle.
+fn reassemble_frames(
+    buf: &mut Vec<u8>,
+    bytes: &[u8],
+    framing: FramingMode,
+    max_frame_size: usize,
+) -> Vec<Vec<u8>> {
+    // No cross-notification accumulation in this mode: each notification
+    // is its own frame, independent of whatever's left in the buffer.
+    if let FramingMode::WholePacket = framing {
+        return vec![bytes.to_vec()];
+    }
+
+    buf.extend_from_slice(bytes);
+    let mut frames = Vec::new();
+
+    match framing {
+        FramingMode::WholePacket => unreachable!("handled above"),
+        FramingMode::Delimited(delim) => {
+            while let Some(pos) = buf.iter().position(|&b| b == delim) {
+                let rest = buf.split_off(pos + 1);
+                let mut frame = std::mem::replace(buf, rest);
+                frame.pop(); // drop the trailing delimiter
+                frames.push(frame);
+            }
+        }
+        FramingMode::LengthPrefixed => {
+            loop {
+                if buf.len() < 2 {
+                    break;
+                }
+                let declared_len = u16::from_be_bytes([buf[0], buf[1]]) as usize;
+                // Reject as soon as the prefix arrives a frame that, with its
+                // 2-byte prefix, cannot fit in max_frame_size, rather than
+                // accumulating it until the buffer check below wipes it.
+                // A frame that passes here is never wiped mid-accumulation.
+                if 2 + declared_len > max_frame_size {
+                    warn!(
+                        "Length-prefixed frame needs {} bytes with its prefix, exceeding max_frame_size ({}); dropping frame",
+                        2 + declared_len, max_frame_size
+                    );
+                    buf.clear();
+                    break;
+                }
+                if buf.len() < 2 + declared_len {
+                    break;
+                }
+                let rest = buf.split_off(2 + declared_len);
+                let frame = std::mem::replace(buf, rest);
+                frames.push(frame[2..].to_vec());
+            }
+        }
+    }
+
+    if buf.len() > max_frame_size {
+        warn!(
+            "Reassembly buffer exceeded max_frame_size ({} > {}); dropping in-flight frame",
+            buf.len(),
+            max_frame_size
+        );
+        buf.clear();
+    }
+
+    frames
 }
 
 pub struct BleCentral {
-    manager: Option<Manager>,               // the BLE Manager, once instantiated
-    adapter: Option<Adapter>,               // the BLE adapter, once instantiated
-    peripheral: Option<Peripheral>,         // the connected peripheral, once found
-    characteristic: Option<Characteristic>, // the discovered characteristic, once found
-    service_uuid: Uuid,                     // the service UUID to discover
-    char_uuid: Uuid,                        // the characteristic UUID to discover
+    manager: Option<Manager>,                  // the BLE Manager, once instantiated
+    adapter: Option<Adapter>,                   // the BLE adapter, once instantiated
+    peripherals: Vec<ConnectedPeripheral>,      // the connected peripheral(s), once found
+    known_ids: Vec<PeripheralId>,                // stable ids of the last peripherals we connected to
+    service_uuid: Uuid,                         // the service UUID to discover
+    char_uuid: Uuid,                            // the notify characteristic UUID to discover
+    write_char_uuid: Option<Uuid>,              // the write characteristic UUID to discover, if any
+    backoff_attempt: u32,                       // consecutive reconnect failures, for exponential backoff
+    event_tx: Option<mpsc::UnboundedSender<NotificationEvent>>, // consumer channel, once subscribed
+    codec: Box<dyn PayloadCodec>,                // decodes reassembled frames per config.codec
     config: BleConfig,
 }
 
 impl BleCentral {
-    
+
     /// Construct and initialize logging + BLE manager + adapter
-    pub async fn new(service: &str, characteristic: &str, config: Option<BleConfig>) -> Result<Self, BleError> {
+    pub async fn new(
+        service: &str,
+        characteristic: &str,
+        write_characteristic: Option<&str>,
+        config: Option<BleConfig>,
+    ) -> Result<Self, BleError> {
         info!("Constructing BLE Central.");
         /*
         * Step 1: Parse the UUIDs
-        * - We define 128-bit UUIDs for the BLE service and characteristic.
+        * - We define 128-bit UUIDs for the BLE service and characteristic(s).
         * - Uuid::parse_str parses a hyphenated string into a Uuid instance.
         * - These must match the peripheral (Android) side exactly.
         */
+        let write_char_uuid = write_characteristic
+            .map(Uuid::parse_str)
+            .transpose()?;
+        let config = config.unwrap_or_default();
+        let codec: Box<dyn PayloadCodec> = match config.codec {
+            CodecKind::Json => Box::new(JsonCodec),
+            CodecKind::Raw => Box::new(RawCodec),
+            CodecKind::Cbor => Box::new(CborCodec),
+            CodecKind::Protobuf => Box::new(ProtobufCodec),
+        };
         Ok(Self {
             manager: None,
             adapter: None,
-            peripheral: None,
-            characteristic: None,
+            peripherals: Vec::new(),
+            known_ids: Vec::new(),
             service_uuid: Uuid::parse_str(service)?,
             char_uuid: Uuid::parse_str(characteristic)?,
-            config: config.unwrap_or_default(),
+            write_char_uuid,
+            backoff_attempt: 0,
+            event_tx: None,
+            codec,
+            config,
         })
     }
 
+    /// Subscribe to parsed notification events.
+    ///
+    /// Only one subscriber is supported at a time — calling this again
+    /// replaces the previous sender, so the old receiver stops getting events.
+    /// Until something subscribes, reassembled notifications are simply
+    /// logged and dropped, as before.
+    pub fn subscribe(&mut self) -> mpsc::UnboundedReceiver<NotificationEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.event_tx = Some(tx);
+        rx
+    }
+
     /// recreate my bluetooth adapater and store in self.adapter
-    #[instrument(skip(self))]    
+    #[instrument(skip(self))]
     async fn recreate_adapter(&mut self) -> Result<(), BleError> {
         info!("Recreating BLE Adapter.");
         /*
@@ -80,133 +292,303 @@ impl BleCentral {
         Ok(())
     }
 
-    /// Scan until we find the peripheral, then store it in self.peripheral
+    /// List every peripheral currently advertising the configured service,
+    /// ranked strongest-signal-first.
+    ///
+    /// Unlike `scan_and_select`, this doesn't store the matches in
+    /// `self.peripherals`, and neither connects to anything — it's
+    /// meant for callers that want to choose among several identical
+    /// sensors (e.g. by RSSI or manufacturer data) before connecting.
+    #[instrument(skip(self))]
+    pub async fn scan(&self) -> Result<Vec<ScanResult>, BleError> {
+        let adapt = self.adapter
+            .as_ref()
+            .ok_or(BleError::NoAdapter)?;
+
+        let filter = ScanFilter { services: vec![self.service_uuid] };
+        adapt.start_scan(filter).await?;
+        sleep(self.config.scan_interval).await;
+        let discovered = adapt.peripherals().await?;
+        adapt.stop_scan().await?;
+
+        let mut results = Vec::new();
+        for p in discovered {
+            if let Ok(Some(props)) = p.properties().await {
+                if props.services.contains(&self.service_uuid) {
+                    results.push(ScanResult {
+                        address: props.address,
+                        local_name: props.local_name,
+                        rssi: props.rssi.unwrap_or(i16::MIN),
+                        manufacturer_data: props.manufacturer_data,
+                    });
+                }
+            }
+        }
+        results.sort_by_key(|r| core::cmp::Reverse(r.rssi));
+        Ok(results)
+    }
+
+    /// Scan until we find (a) matching peripheral(s), then store them in
+    /// self.peripherals.
+    ///
+    /// When `config.multi_peripheral` is `false` this stops at the first
+    /// match, as before. When `true` it keeps collecting distinct matches
+    /// for the whole scan window, supporting sensor-network setups where
+    /// several identical peripherals advertise the same service.
     #[instrument(skip(self))]
     async fn scan_and_select(&mut self) -> Result<(), BleError> {
-        info!("Scanning for peripheral.");
+        info!("Scanning for peripheral(s).");
 
         let adapt = self.adapter
             .as_ref()
             .ok_or(BleError::NoAdapter)?;
+        let service_uuid = self.service_uuid;
+        let multi = self.config.multi_peripheral;
 
         /*
         * Step 3: Start scanning for peripherals advertising our service UUID
         * - ScanFilter configures the BLE library to only return advertisements containing our service.
         * - adapter.start_scan triggers the OS BLE scan.
+        * - adapter.events() gives us a push-based stream of discovery events
+        *   instead of having to re-poll adapter.peripherals() on a timer.
         */
-        let filter = ScanFilter { services: vec![self.service_uuid], ..Default::default() };        
-        
+        let filter = ScanFilter { services: vec![service_uuid] };
+        let mut events = adapt.events().await?;
+
         adapt.start_scan(filter).await?;
-        debug!("Started Scanning for BLE peripheral…");
+        debug!("Started Scanning for BLE peripheral(s)…");
 
         /*
-        * Step 4: Poll until we discover our target peripheral (with timeout)
-        * - Loop with a cap on attempts (30 seconds max).
-        * - adapter.peripherals() lists discovered devices so far.
-        * - p.properties().await fetches advertisement metadata including services.
-        * - We compare the advertised services list to our target UUID.
+        * Step 4: Wait for DeviceDiscovered/DeviceUpdated events whose
+        * advertised services include our target, bounded by a single
+        * timeout covering the whole scan window. In single-peripheral mode
+        * we stop at the first match; in multi-peripheral mode we keep
+        * collecting distinct matches until the window elapses.
         */
-        'scan: for _ in 0..self.config.scan_retries {
-            let list = adapt.peripherals().await?;
-            for p in &list {
-                // Perform the async properties() call outside of a closure
-                if let Ok(Some(props)) = p.properties().await {
-                    if props.services.contains(&self.service_uuid) {
-                        self.peripheral = Some(p.clone());
-                        info!("Found peripheral {}", p.address());
-                        break 'scan;
+        let scan_window = self.config.scan_interval * self.config.scan_retries;
+        let mut found: Vec<Peripheral> = Vec::new();
+        let timed_out = timeout(scan_window, async {
+            while let Some(event) = events.next().await {
+                let id = match event {
+                    CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id) => id,
+                    _ => continue,
+                };
+                let Ok(periph) = adapt.peripheral(&id).await else {
+                    continue;
+                };
+                if found.iter().any(|p| p.address() == periph.address()) {
+                    continue;
+                }
+                if let Ok(Some(props)) = periph.properties().await {
+                    if props.services.contains(&service_uuid) {
+                        info!("Found peripheral {}", periph.address());
+                        found.push(periph);
+                        if !multi {
+                            return;
+                        }
                     }
                 }
             }
-            // sleep for 1s before trying again
-            debug!("no peripheral found. sleep and retry");
-            sleep(self.config.scan_interval).await;
-        }
+        }).await.is_err();
 
         adapt.stop_scan().await?;
         debug!("Stopped scanning.");
 
-        self.peripheral
+        if found.is_empty() {
+            return Err(if timed_out {
+                BleError::ScanTimeout(scan_window.as_secs())
+            } else {
+                BleError::NoPeripheral
+            });
+        }
+
+        self.known_ids = found.iter().map(|p| p.id()).collect();
+        self.peripherals = found.into_iter().map(ConnectedPeripheral::new).collect();
+        Ok(())
+    }
+
+    /// Try to re-locate and connect to the peripherals we were connected to
+    /// last time, by their stable `PeripheralId`, without rescanning.
+    ///
+    /// This follows the bluest reconnect pattern: a peripheral that merely
+    /// dropped link momentarily usually still resolves via
+    /// `adapter.peripheral(&id)`, which is much faster than a full scan.
+    #[instrument(skip(self))]
+    async fn reconnect_known(&mut self) -> Result<(), BleError> {
+        if self.known_ids.is_empty() {
+            return Err(BleError::NoPeripheral);
+        }
+
+        let adapt = self.adapter
             .as_ref()
-            .ok_or(BleError::NoPeripheral)?;
-        
+            .ok_or(BleError::NoAdapter)?;
+
+        let mut reconnected = Vec::new();
+        for id in &self.known_ids {
+            match adapt.peripheral(id).await {
+                Ok(periph) => reconnected.push(periph),
+                Err(e) => debug!("Could not re-locate known peripheral {:?}: {}", id, e),
+            }
+        }
+
+        if reconnected.is_empty() {
+            return Err(BleError::NoPeripheral);
+        }
+
+        info!("Reconnecting to {} known peripheral(s) without rescanning", reconnected.len());
+        self.peripherals = reconnected.into_iter().map(ConnectedPeripheral::new).collect();
         Ok(())
+    }
+
+    /// Current exponential backoff delay for `backoff_attempt` consecutive
+    /// failures, capped at `config.reconnect_backoff_max`.
+    fn backoff_delay(&self) -> Duration {
+        let multiplier = 1u32.checked_shl(self.backoff_attempt).unwrap_or(u32::MAX);
+        self.config.reconnect_backoff
+            .saturating_mul(multiplier)
+            .min(self.config.reconnect_backoff_max)
+    }
 
+    /// Sleep for the current backoff delay, then grow it for next time.
+    async fn sleep_backoff(&mut self) {
+        let delay = self.backoff_delay();
+        debug!("Backing off for {:?} (attempt {})", delay, self.backoff_attempt);
+        sleep(delay).await;
+        self.backoff_attempt = self.backoff_attempt.saturating_add(1);
     }
 
-    /// connect to the peripheral and discover its services
+    /// connect to the peripheral(s) and discover their services
     #[instrument(skip(self))]
     async fn connect_and_discover(&mut self) -> Result<(), BleError> {
-        info!("Connecting to peripheral and discovering services.");
-        
-        let periph = self.peripheral
-            .as_ref()
-            .ok_or(BleError::NoPeripheral)
-            .unwrap();
+        if self.peripherals.is_empty() {
+            return Err(BleError::NoPeripheral);
+        }
+        info!("Connecting to {} peripheral(s) and discovering services.", self.peripherals.len());
 
-        /*
-        * Step 5: Connect to the peripheral and discover its services
-        * - peripheral.connect() establishes a GATT connection.
-        * - peripheral.discover_services() populates the GATT service and characteristic cache.
-        */
-        periph.connect().await?;
-        debug!("Connected to {:?}", periph.address());
-        periph.discover_services().await?;
-        debug!("Services discovered");
+        for cp in &mut self.peripherals {
+            let periph = &cp.peripheral;
 
-        /*
-        * Step 6: Locate the specific GATT characteristic to subscribe to
-        * - peripheral.characteristics() returns a Vec of all characteristics.
-        * - We find the one matching our UUID and clone it for use.
-        */
-        let chars = periph.characteristics();
-        self.characteristic = chars.iter()
-            .find(|c| c.uuid == self.char_uuid)
-            .cloned();
+            /*
+            * Step 5: Connect to the peripheral and discover its services
+            * - peripheral.connect() establishes a GATT connection.
+            * - peripheral.discover_services() populates the GATT service and characteristic cache.
+            */
+            periph.connect().await?;
+            debug!("Connected to {:?}", periph.address());
+            periph.discover_services().await?;
+            debug!("Services discovered for {:?}", periph.address());
+
+            /*
+            * Step 6: Locate the specific GATT characteristic(s) to use
+            * - peripheral.characteristics() returns a Vec of all characteristics.
+            * - We find the ones matching our UUIDs and clone them for use.
+            */
+            let chars = periph.characteristics();
+            cp.characteristic = chars.iter()
+                .find(|c| c.uuid == self.char_uuid)
+                .cloned();
+
+            if let Some(write_uuid) = self.write_char_uuid {
+                cp.write_characteristic = chars.iter()
+                    .find(|c| c.uuid == write_uuid)
+                    .cloned();
+                debug!("Write characteristic discovered: {}", cp.write_characteristic.is_some());
+            }
+        }
 
         Ok(())
+    }
 
+    /// Write a payload to every connected peripheral's write characteristic
+    ///
+    /// Uses `WriteType::WithoutResponse`, matching the Meshtastic TORADIO
+    /// characteristic this crate's write side is modeled on.
+    #[instrument(skip(self, payload))]
+    pub async fn send(&self, payload: &[u8]) -> Result<(), BleError> {
+        let mut wrote_any = false;
+        for cp in &self.peripherals {
+            if let Some(tx_char) = &cp.write_characteristic {
+                cp.peripheral.write(tx_char, payload, WriteType::WithoutResponse).await?;
+                debug!("Wrote {} bytes to {} ({})", payload.len(), tx_char.uuid, cp.peripheral.address());
+                wrote_any = true;
+            }
+        }
+
+        if !wrote_any {
+            return Err(BleError::NoWriteCharacteristic);
+        }
+
+        Ok(())
     }
 
-    /// Connect, discover, subscribe, and process notifications
+    /// Subscribe to and process notifications from every connected
+    /// peripheral concurrently
     #[instrument(skip(self))]
     async fn run_session(&mut self) -> Result<(), BleError> {
-        info!("Starting session.");
-
-        // proceed only if we have a reference to the peripheral
-        let periph = self.peripheral
-            .as_ref()
-            .ok_or(BleError::NoPeripheral)?;
-
-        let tx_char = self.characteristic
-            .as_ref()
-            .ok_or(BleError::NoCharacteristic(self.char_uuid))?;
+        if self.peripherals.is_empty() {
+            return Err(BleError::NoPeripheral);
+        }
+        info!("Starting session with {} peripheral(s).", self.peripherals.len());
 
         /*
-        * Step 7: Subscribe to notifications on that characteristic
+        * Step 7: Subscribe to notifications on each peripheral
         * - peripheral.notifications() yields a stream of incoming notifications.
         * - peripheral.subscribe() writes to the CCCD descriptor to enable notifications.
+        * - We tag each stream's items with the originating address and merge
+        *   them with stream::select_all so all peripherals are read concurrently.
         */
-        let mut notifications = periph.notifications().await?; 
-        debug!("Attempting to subscribe…");
-        periph.subscribe(tx_char).await?;
-        info!("Subscribed to notifications on {}", self.char_uuid);
+        let mut streams = Vec::new();
+        for cp in &self.peripherals {
+            let tx_char = cp.characteristic
+                .as_ref()
+                .ok_or(BleError::NoCharacteristic(self.char_uuid))?;
+            let notifications = cp.peripheral.notifications().await?;
+            debug!("Attempting to subscribe to {:?}…", cp.peripheral.address());
+            cp.peripheral.subscribe(tx_char).await?;
+            let addr = cp.peripheral.address();
+            streams.push(notifications.map(move |n| (addr, n)));
+        }
+        info!("Subscribed to notifications on {} peripheral(s)", self.peripherals.len());
+        let mut merged = stream::select_all(streams);
 
         /*
         * Step 8: Process incoming notification packets
-        * - We loop on notifications.next() which awaits the next notification.
+        * - We loop on merged.next() which awaits the next notification from any peripheral.
         * - Each notification has a UUID and raw byte Vec payload.
-        * - We convert it to UTF-8, then parse as JSON using serde_json.
+        * - Payloads may be fragmented across several notifications, so we
+        *   feed each one through the originating peripheral's reassembly
+        *   buffer and only hand the configured codec a whole frame once complete.
         */
-        debug!("Listening for JSON notifications…");
+        debug!("Listening for notifications…");
+        let (framing, max_frame_size) = (self.config.framing, self.config.max_frame_size);
         loop {
-            match timeout(self.config.notify_timeout,notifications.next()).await {
-                Ok(Some(n)) => {
+            match timeout(self.config.notify_timeout, merged.next()).await {
+                Ok(Some((addr, n))) => {
                     if n.uuid == self.char_uuid {
-                        let text = String::from_utf8_lossy(&n.value);
-                        match serde_json::from_str::<Value>(&text) {
-                            Ok(json) => info!("→ {}", json),
-                            Err(e)   => error!("JSON parse error: {}", e),
+                        let characteristic = n.uuid;
+                        if let Some(cp) = self.peripherals.iter_mut().find(|cp| cp.peripheral.address() == addr) {
+                            for frame in cp.reassemble(&n.value, framing, max_frame_size) {
+                                match self.codec.decode(&frame) {
+                                    Ok(payload) => {
+                                        info!("[{}] → {:?}", addr, payload);
+                                        if let Some(tx) = &self.event_tx {
+                                            let _ = tx.send(NotificationEvent {
+                                                source: addr,
+                                                characteristic,
+                                                raw: frame,
+                                                payload,
+                                            });
+                                        }
+                                    }
+                                    // Logged and skipped rather than surfaced as
+                                    // Err(BleError::Codec) — one malformed frame
+                                    // shouldn't tear down the whole session, the
+                                    // same tradeoff the original JSON parsing made.
+                                    // BleError::Codec exists for callers building
+                                    // on `PayloadCodec` directly outside this loop.
+                                    Err(e) => error!("[{}] Codec decode error: {}", addr, e),
+                                }
+                            }
                         }
                     }
                 },
@@ -225,29 +607,49 @@ impl BleCentral {
         loop {
             // 1) Recreate Adapter
             if let Err(e) = self.recreate_adapter().await {
-                warn!("Failed to get adapter: {} — retrying in 5s", e);
-                sleep(self.config.reconnect_backoff).await;
+                warn!("Failed to get adapter: {} — retrying…", e);
+                self.sleep_backoff().await;
                 continue;
             }
-            // 2) Scan & select
-            if let Err(e) = self.scan_and_select().await {
-                warn!("Scan failed: {} — retrying in 5s…", e);
-                sleep(self.config.reconnect_backoff).await;
-                continue;
+
+            // 2) Fast-path reconnect to known peripherals; fall back to a
+            //    full rescan if we have no known ids or they no longer resolve.
+            let reconnected = self.reconnect_known().await.is_ok();
+            if !reconnected {
+                if let Err(e) = self.scan_and_select().await {
+                    warn!("Scan failed: {} — retrying…", e);
+                    self.sleep_backoff().await;
+                    continue;
+                }
             }
+
             // 3) Connect & discover
             if let Err(e) = self.connect_and_discover().await {
-                warn!("Discover failed: {} — retrying in 5s…", e);
-                sleep(self.config.reconnect_backoff).await;
+                warn!("Discover failed: {} — retrying…", e);
+                self.peripherals.clear();
+                if reconnected {
+                    // The fast path resolved these ids (e.g. CoreBluetooth
+                    // handed back a cached handle) but we still couldn't
+                    // actually connect — the device may be gone or have
+                    // re-addressed, so drop the stale ids and force a
+                    // fresh scan next time instead of looping forever.
+                    debug!("Fast-path reconnect ids no longer connectable; forcing a rescan");
+                    self.known_ids.clear();
+                }
+                self.sleep_backoff().await;
                 continue;
             }
+
+            // A fully successful connect resets the backoff ladder.
+            self.backoff_attempt = 0;
+
             // 4) Run session
             if let Err(e) = self.run_session().await {
-                warn!("Session error: {} — retrying in 5s…", e);
-                // drop old peripheral & characteristic
-                self.peripheral = None;
-                self.characteristic = None;
-                sleep(self.config.reconnect_backoff).await;
+                warn!("Session error: {} — retrying…", e);
+                // drop old peripherals; the next loop tries the known ids
+                // first and rescans only if they no longer resolve
+                self.peripherals.clear();
+                self.sleep_backoff().await;
                 continue;
             }
             // if run_session() ever returns Ok, we exit the loop
@@ -260,15 +662,87 @@ impl BleCentral {
     #[instrument(skip(self))]
     pub async fn shutdown(&mut self) {
         info!("Shutting down BLE.");
-        if let Some(per) = &self.peripheral {
-            if let Some(tx_char) = &self.characteristic {
-                let _ = per.unsubscribe(tx_char);
-                debug!("Unsubscribed");
+        for cp in &self.peripherals {
+            if let Some(tx_char) = &cp.characteristic {
+                let _ = cp.peripheral.unsubscribe(tx_char).await;
+                debug!("Unsubscribed from {:?}", cp.peripheral.address());
             }
-            let _ = per.disconnect();
-            debug!("Disconnected");
-            debug!("Performed shutdown cleanup");
+            let _ = cp.peripheral.disconnect().await;
+            debug!("Disconnected {:?}", cp.peripheral.address());
         }
+        debug!("Performed shutdown cleanup");
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_packet_treats_every_call_as_its_own_frame() {
+        let mut buf = Vec::new();
+        let frames = reassemble_frames(&mut buf, b"hello", FramingMode::WholePacket, 4096);
+        assert_eq!(frames, vec![b"hello".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn delimited_splits_on_delimiter_and_drops_it() {
+        let mut buf = Vec::new();
+        let frames = reassemble_frames(&mut buf, b"abc\ndef\nghi", FramingMode::Delimited(b'\n'), 4096);
+        assert_eq!(frames, vec![b"abc".to_vec(), b"def".to_vec()]);
+        assert_eq!(buf, b"ghi");
+    }
+
+    #[test]
+    fn delimited_accumulates_a_frame_split_across_calls() {
+        let mut buf = Vec::new();
+        assert!(reassemble_frames(&mut buf, b"ab", FramingMode::Delimited(b'\n'), 4096).is_empty());
+        let frames = reassemble_frames(&mut buf, b"c\n", FramingMode::Delimited(b'\n'), 4096);
+        assert_eq!(frames, vec![b"abc".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn delimited_drops_buffer_once_it_exceeds_max_frame_size() {
+        let mut buf = Vec::new();
+        let frames = reassemble_frames(&mut buf, &[0u8; 10], FramingMode::Delimited(b'\n'), 4);
+        assert!(frames.is_empty());
+        assert!(buf.is_empty());
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn length_prefixed_waits_for_the_full_frame_before_emitting() {
+        let mut buf = Vec::new();
+        // declares 3 payload bytes but only 2 have arrived so far
+        let frames = reassemble_frames(&mut buf, &[0, 3, b'a', b'b'], FramingMode::LengthPrefixed, 4096);
+        assert!(frames.is_empty());
+
+        let frames = reassemble_frames(&mut buf, b"c", FramingMode::LengthPrefixed, 4096);
+        assert_eq!(frames, vec![b"abc".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn length_prefixed_drains_every_complete_frame_in_one_call() {
+        let mut buf = Vec::new();
+        let mut bytes = vec![0, 2];
+        bytes.extend_from_slice(b"hi");
+        bytes.extend_from_slice(&[0, 3]);
+        bytes.extend_from_slice(b"bye");
+
+        let frames = reassemble_frames(&mut buf, &bytes, FramingMode::LengthPrefixed, 4096);
+        assert_eq!(frames, vec![b"hi".to_vec(), b"bye".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn length_prefixed_rejects_a_declared_length_over_max_frame_size() {
+        let mut buf = Vec::new();
+        // declares 10 payload bytes, which exceeds max_frame_size of 4
+        let frames = reassemble_frames(&mut buf, &[0, 10, b'x', b'y'], FramingMode::LengthPrefixed, 4);
+        assert!(frames.is_empty());
+        assert!(buf.is_empty());
+    }
+}
@@ -15,9 +15,15 @@ pub enum BleError {
     #[error("Characteristic not found: {0}")]
     NoCharacteristic(uuid::Uuid),
 
+    #[error("No write characteristic configured or discovered")]
+    NoWriteCharacteristic,
+
     #[error("BLE operation failed: {0}")]
     Api(#[from] btleplug::Error),
 
     #[error("Session ended (peripheral disconnected or adapter lost)")]
     SessionEnded,
+
+    #[error("Payload decode error: {0}")]
+    Codec(#[from] crate::codec::CodecError),
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+pub mod ble_central;
+pub mod codec;
+pub mod error;
+
+pub use ble_central::{
+    BleCentral, BleConfig, CodecKind, FramingMode, NotificationEvent, ScanResult,
+};
+pub use codec::{CborCodec, DecodedPayload, JsonCodec, PayloadCodec, ProtobufCodec, RawCodec};
+pub use error::BleError;